@@ -1,7 +1,37 @@
 use thiserror::Error;
 
+/// Which dimension of a matrix an out-of-bounds index fell on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+impl std::fmt::Display for Axis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Axis::X => write!(f, "x"),
+            Axis::Y => write!(f, "y"),
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum BoundError {
-    #[error("out of bounds, got index `({0}, {1})`")]
-    Exceed(usize, usize),
+    #[error("index `{index}` exceeds the maximum of `{max}` along the `{axis}` axis")]
+    Exceed {
+        axis: Axis,
+        index: usize,
+        max: usize,
+    },
+}
+
+/// Errors that can arise while round-tripping a matrix through its
+/// sparse, `bincode`-oriented wire format.
+#[derive(Debug, Error)]
+pub enum SparseError {
+    #[error(transparent)]
+    Bound(#[from] BoundError),
+    #[error(transparent)]
+    Bincode(#[from] bincode::Error),
 }
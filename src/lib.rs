@@ -0,0 +1,3 @@
+pub mod error;
+pub mod matrix;
+pub mod static_matrix;
@@ -1,6 +1,12 @@
-use crate::error::BoundError;
+use crate::error::{Axis, BoundError, SparseError};
+use arbitrary::{Arbitrary, Unstructured};
 use std::fmt::Debug;
 
+/// Upper bound placed on each dimension when generating a matrix from
+/// arbitrary/fuzzer input, so a handful of unlucky bytes can't demand a
+/// multi-gigabyte allocation.
+const MAX_DIM: usize = 64;
+
 #[derive(serde::Deserialize, serde::Serialize, Debug)]
 pub struct Matrix<T>
 where
@@ -32,18 +38,54 @@ impl<T: Debug + Default + Clone + serde::ser::Serialize> Matrix<T> {
         }
     }
 
+    /// Check that `x` and `y` are both in bounds, otherwise report which
+    /// axis was exceeded and by what maximum.
+    fn check_bounds(&self, x: usize, y: usize) -> Result<(), BoundError> {
+        if x >= self.x_dim {
+            return Err(BoundError::Exceed {
+                axis: Axis::X,
+                index: x,
+                max: self.x_dim,
+            });
+        }
+        if y >= self.y_dim {
+            return Err(BoundError::Exceed {
+                axis: Axis::Y,
+                index: y,
+                max: self.y_dim,
+            });
+        }
+        Ok(())
+    }
+
     /// Insert an element into the matrix at (x, y)
     /// and send an error result if the conte
     pub fn add(&mut self, x: usize, y: usize, value: Option<T>) -> Result<(), BoundError> {
-        if x > self.x_dim || y > self.y_dim {
-            return Err(BoundError::Exceed(x, y));
-        }
+        self.check_bounds(x, y)?;
 
-        // It is safe to just unwrap here, we checked the bounds above
-        *self.content.get_mut(x).unwrap().get_mut(y).unwrap() = value;
+        // It is safe to just unwrap here, we checked the bounds above.
+        // `content` is laid out `y_dim` rows of `x_dim` columns, so `y`
+        // indexes the outer `Vec` and `x` the inner one.
+        *self.content.get_mut(y).unwrap().get_mut(x).unwrap() = value;
         Ok(())
     }
 
+    /// Insert an element into the matrix at (x, y) without checking
+    /// bounds. Under `debug_assertions` the invariant is still asserted;
+    /// in release builds an out-of-range index is undefined behavior, so
+    /// only use this on a hot path where the index has already been
+    /// validated.
+    ///
+    /// # Safety
+    ///
+    /// `x` must be `< self.x_dim` and `y` must be `< self.y_dim`.
+    pub unsafe fn add_unchecked(&mut self, x: usize, y: usize, value: Option<T>) {
+        debug_assert!(x < self.x_dim && y < self.y_dim);
+        unsafe {
+            *self.content.get_unchecked_mut(y).get_unchecked_mut(x) = value;
+        }
+    }
+
     /// Override an element from the matrix at (x, y)
     /// and insert the default value to take it's place
     pub fn remove(&mut self, x: usize, y: usize) -> Result<(), BoundError> {
@@ -52,12 +94,66 @@ impl<T: Debug + Default + Clone + serde::ser::Serialize> Matrix<T> {
 
     /// Get an immutable reference to an element at (x, y)
     pub fn get(&self, x: usize, y: usize) -> Result<&Option<T>, BoundError> {
-        if x > self.x_dim || y > self.y_dim {
-            return Err(BoundError::Exceed(x, y));
-        }
+        self.check_bounds(x, y)?;
+
+        // It is safe to just unwrap here, we checked the bounds above.
+        // `content` is laid out `y_dim` rows of `x_dim` columns, so `y`
+        // indexes the outer `Vec` and `x` the inner one.
+        Ok(self.content.get(y).unwrap().get(x).unwrap())
+    }
+
+    /// Get a mutable reference to an element at (x, y)
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Result<&mut Option<T>, BoundError> {
+        self.check_bounds(x, y)?;
 
         // It is safe to just unwrap here, we checked the bounds above
-        Ok(self.content.get(x).unwrap().get(y).unwrap())
+        Ok(self.content.get_mut(y).unwrap().get_mut(x).unwrap())
+    }
+
+    /// Get an immutable reference to an element at (x, y) without
+    /// checking bounds. Under `debug_assertions` the invariant is still
+    /// asserted; in release builds an out-of-range index is undefined
+    /// behavior, so only use this on a hot path where the index has
+    /// already been validated.
+    ///
+    /// # Safety
+    ///
+    /// `x` must be `< self.x_dim` and `y` must be `< self.y_dim`.
+    pub unsafe fn get_unchecked(&self, x: usize, y: usize) -> &Option<T> {
+        debug_assert!(x < self.x_dim && y < self.y_dim);
+        unsafe { self.content.get_unchecked(y).get_unchecked(x) }
+    }
+
+    /// Iterate over the underlying container by column number, without
+    /// allocating, yielding immutable references in row order.
+    /// If the column number supplied is too large, exit and complain
+    /// through the use of the `BoundError`
+    pub fn col_iter(&self, col: usize) -> Result<impl Iterator<Item = &Option<T>>, BoundError> {
+        if col >= self.x_dim {
+            return Err(BoundError::Exceed {
+                axis: Axis::X,
+                index: col,
+                max: self.x_dim,
+            });
+        }
+
+        Ok((0..self.y_dim).map(move |pos| self.get(col, pos).unwrap()))
+    }
+
+    /// Iterate over the underlying container by row number, without
+    /// allocating, yielding immutable references in column order.
+    /// If the row number supplied is too large, exit and complain
+    /// through the use of the `BoundError`
+    pub fn row_iter(&self, row: usize) -> Result<impl Iterator<Item = &Option<T>>, BoundError> {
+        if row >= self.y_dim {
+            return Err(BoundError::Exceed {
+                axis: Axis::Y,
+                index: row,
+                max: self.y_dim,
+            });
+        }
+
+        Ok((0..self.x_dim).map(move |pos| self.get(pos, row).unwrap()))
     }
 
     /// Obtain a Vector of immutable references to the underlying
@@ -65,28 +161,233 @@ impl<T: Debug + Default + Clone + serde::ser::Serialize> Matrix<T> {
     /// If the column number supplied is too large, exit and complain
     /// through the use of the `BoundError`
     pub fn col(&self, col: usize) -> Result<Vec<&Option<T>>, BoundError> {
-        if col > self.y_dim {
-            return Err(BoundError::Exceed(0, col));
-        }
-
-        Ok((0..self.y_dim)
-            .into_iter()
-            .map(|pos| self.get(pos, col).unwrap())
-            .collect())
+        Ok(self.col_iter(col)?.collect())
     }
+
     /// Obtain a Vector of immutable references to the underlying
     /// container when given the row number.
     /// If the row number supplied is too large, exit and complain
     /// through the use of the `BoundError`
     pub fn row(&self, row: usize) -> Result<Vec<&Option<T>>, BoundError> {
-        if row > self.x_dim {
-            return Err(BoundError::Exceed(self.x_dim, self.y_dim));
+        Ok(self.row_iter(row)?.collect())
+    }
+
+    /// Iterate over every cell of the matrix without allocating, yielding
+    /// its `(x, y)` coordinate alongside an immutable reference to it.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &Option<T>)> {
+        (0..self.y_dim)
+            .flat_map(move |y| (0..self.x_dim).map(move |x| (x, y)))
+            .map(move |(x, y)| ((x, y), self.get(x, y).unwrap()))
+    }
+
+    /// Borrow a rectangular region of the matrix without copying it.
+    /// `x_range`/`y_range` are validated against `x_dim`/`y_dim` up front,
+    /// so every access through the returned [`MatrixView`] is in-bounds by
+    /// construction.
+    pub fn submatrix(
+        &self,
+        x_range: std::ops::Range<usize>,
+        y_range: std::ops::Range<usize>,
+    ) -> Result<MatrixView<'_, T>, BoundError> {
+        if x_range.end > self.x_dim {
+            return Err(BoundError::Exceed {
+                axis: Axis::X,
+                index: x_range.end,
+                max: self.x_dim,
+            });
+        }
+        if y_range.end > self.y_dim {
+            return Err(BoundError::Exceed {
+                axis: Axis::Y,
+                index: y_range.end,
+                max: self.y_dim,
+            });
         }
 
-        Ok((0..self.x_dim)
-            .into_iter()
-            .map(|pos| self.get(row, pos).unwrap())
-            .collect())
+        Ok(MatrixView {
+            matrix: self,
+            x_origin: x_range.start,
+            y_origin: y_range.start,
+            x_dim: x_range.len(),
+            y_dim: y_range.len(),
+        })
+    }
+}
+
+impl<'a, T: Debug + Default + Clone + serde::ser::Serialize> IntoIterator for &'a Matrix<T> {
+    type Item = ((usize, usize), &'a Option<T>);
+    type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+/// A borrowing view over a rectangular region of a [`Matrix`]. Coordinates
+/// passed to its methods are relative to the view's own origin, not the
+/// parent matrix, and share the parent's [`BoundError`] semantics. Useful
+/// for block-wise algorithms (tiling, neighborhood scans) that need to
+/// operate on a region while leaving the parent matrix intact.
+pub struct MatrixView<'a, T>
+where
+    T: Clone + Debug + Default + serde::ser::Serialize,
+{
+    matrix: &'a Matrix<T>,
+    x_origin: usize,
+    y_origin: usize,
+    x_dim: usize,
+    y_dim: usize,
+}
+
+impl<'a, T: Debug + Default + Clone + serde::ser::Serialize> MatrixView<'a, T> {
+    /// Get an immutable reference to an element at (x, y), relative to
+    /// this view's origin
+    pub fn get(&self, x: usize, y: usize) -> Result<&'a Option<T>, BoundError> {
+        if x >= self.x_dim {
+            return Err(BoundError::Exceed {
+                axis: Axis::X,
+                index: x,
+                max: self.x_dim,
+            });
+        }
+        if y >= self.y_dim {
+            return Err(BoundError::Exceed {
+                axis: Axis::Y,
+                index: y,
+                max: self.y_dim,
+            });
+        }
+
+        self.matrix.get(self.x_origin + x, self.y_origin + y)
+    }
+
+    /// Obtain a Vector of immutable references to the view's underlying
+    /// cells when given the column number, relative to this view's origin
+    pub fn col(&self, col: usize) -> Result<Vec<&'a Option<T>>, BoundError> {
+        if col >= self.x_dim {
+            return Err(BoundError::Exceed {
+                axis: Axis::X,
+                index: col,
+                max: self.x_dim,
+            });
+        }
+
+        (0..self.y_dim).map(|pos| self.get(col, pos)).collect()
+    }
+
+    /// Obtain a Vector of immutable references to the view's underlying
+    /// cells when given the row number, relative to this view's origin
+    pub fn row(&self, row: usize) -> Result<Vec<&'a Option<T>>, BoundError> {
+        if row >= self.y_dim {
+            return Err(BoundError::Exceed {
+                axis: Axis::Y,
+                index: row,
+                max: self.y_dim,
+            });
+        }
+
+        (0..self.x_dim).map(|pos| self.get(pos, row)).collect()
+    }
+
+    /// Iterate over every cell of the view without allocating, yielding
+    /// its `(x, y)` coordinate (relative to the view's origin) alongside
+    /// an immutable reference to it.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &'a Option<T>)> + 'a {
+        let matrix = self.matrix;
+        let (x_origin, y_origin, x_dim, y_dim) =
+            (self.x_origin, self.y_origin, self.x_dim, self.y_dim);
+
+        (0..y_dim)
+            .flat_map(move |y| (0..x_dim).map(move |x| (x, y)))
+            .map(move |(x, y)| ((x, y), matrix.get(x_origin + x, y_origin + y).unwrap()))
+    }
+}
+
+/// On-the-wire representation of a [`Matrix`] that only records populated
+/// cells, suitable for `bincode`. Dense matrices pay `O(x_dim * y_dim)` to
+/// serialize through `serde_json`; this pays `O(populated)` instead.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct SparseMatrix<T> {
+    x_dim: usize,
+    y_dim: usize,
+    entries: Vec<(usize, usize, T)>,
+}
+
+impl<T: Debug + Default + Clone + serde::ser::Serialize> Matrix<T> {
+    /// Serialize only the populated cells of the matrix, as `(x, y, value)`
+    /// triples alongside its dimensions, using `bincode`.
+    pub fn to_sparse_bytes(&self) -> Result<Vec<u8>, SparseError>
+    where
+        T: serde::Serialize,
+    {
+        let entries = self
+            .iter()
+            .filter_map(|((x, y), cell)| cell.clone().map(|value| (x, y, value)))
+            .collect();
+
+        let sparse = SparseMatrix {
+            x_dim: self.x_dim,
+            y_dim: self.y_dim,
+            entries,
+        };
+
+        Ok(bincode::serialize(&sparse)?)
+    }
+
+    /// Rebuild a matrix from bytes produced by [`Matrix::to_sparse_bytes`].
+    /// Starts from an all-`None` matrix sized per the decoded dimensions
+    /// and replays each entry through [`Matrix::add`], so an out-of-range
+    /// triple surfaces as a [`BoundError`] rather than corrupting memory.
+    pub fn from_sparse_bytes(bytes: &[u8]) -> Result<Self, SparseError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let sparse: SparseMatrix<T> = bincode::deserialize(bytes)?;
+        let mut matrix = Matrix::new(sparse.x_dim, sparse.y_dim);
+
+        for (x, y, value) in sparse.entries {
+            matrix.add(x, y, Some(value))?;
+        }
+
+        Ok(matrix)
+    }
+}
+
+impl<'a, T> Arbitrary<'a> for Matrix<T>
+where
+    T: Debug + Default + Clone + serde::ser::Serialize + Arbitrary<'a>,
+{
+    /// Pull a pair of dimensions (each capped at `MAX_DIM`) out of the
+    /// unstructured buffer, then fill every cell of the resulting matrix
+    /// with an arbitrary `Option<T>`.
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let x_dim = u.int_in_range(0..=MAX_DIM)?;
+        let y_dim = u.int_in_range(0..=MAX_DIM)?;
+
+        let mut content = Vec::with_capacity(y_dim);
+        for _ in 0..y_dim {
+            let mut row = Vec::with_capacity(x_dim);
+            for _ in 0..x_dim {
+                row.push(Option::<T>::arbitrary(u)?);
+            }
+            content.push(row);
+        }
+
+        Ok(Self {
+            content,
+            x_dim,
+            y_dim,
+        })
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        arbitrary::size_hint::and(
+            <usize as Arbitrary>::size_hint(depth),
+            arbitrary::size_hint::and(
+                <usize as Arbitrary>::size_hint(depth),
+                <Option<T> as Arbitrary>::size_hint(depth),
+            ),
+        )
     }
 }
 
@@ -145,10 +446,50 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "Exceed(100, 100)")]
     fn test_out_of_bounds() {
         let mut matrix = Matrix::<usize>::new(1, 1);
-        matrix.add(100, 100, Some(0_usize)).unwrap();
+        let err = matrix.add(100, 100, Some(0_usize)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "index `100` exceeds the maximum of `1` along the `x` axis"
+        );
+    }
+
+    #[test]
+    fn test_non_square_matrix() {
+        let (x_dim, y_dim) = (2, 5);
+        let mut matrix = Matrix::<usize>::new(x_dim, y_dim);
+
+        matrix.add(1, 4, Some(9)).unwrap();
+        assert_eq!(*matrix.get(1, 4).unwrap(), Some(9));
+
+        let err = matrix.add(0, 5, Some(0)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "index `5` exceeds the maximum of `5` along the `y` axis"
+        );
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let mut matrix = Matrix::<usize>::new(2, 2);
+        matrix.add(0, 0, Some(1)).unwrap();
+
+        *matrix.get_mut(0, 0).unwrap() = Some(2);
+
+        assert_eq!(*matrix.get(0, 0).unwrap(), Some(2));
+    }
+
+    #[test]
+    fn test_unchecked_matches_checked() {
+        let mut matrix = Matrix::<usize>::new(2, 2);
+        matrix.add(0, 0, Some(1)).unwrap();
+
+        unsafe {
+            matrix.add_unchecked(1, 1, Some(7));
+            assert_eq!(*matrix.get_unchecked(1, 1), Some(7));
+        }
+        assert_eq!(*matrix.get(1, 1).unwrap(), Some(7));
     }
 
     #[test]
@@ -160,17 +501,169 @@ mod test {
 
         println!("{:?}", matrix.content);
 
+        for (lhs, rhs) in matrix.col(0).unwrap().iter().zip([Some(1), None, None]) {
+            assert_eq!(**lhs, rhs);
+        }
+
         for (lhs, rhs) in matrix
-            .col(0)
+            .row(0)
             .unwrap()
             .iter()
             .zip([Some(1), Some(1), Some(1)])
         {
             assert_eq!(**lhs, rhs);
         }
+    }
 
-        for (lhs, rhs) in matrix.row(0).unwrap().iter().zip([Some(1), None, None]) {
-            assert_eq!(**lhs, rhs);
+    #[test]
+    fn test_row_col_non_square() {
+        let (x_dim, y_dim) = (2, 4);
+        let mut matrix = Matrix::<usize>::new(x_dim, y_dim);
+        for x in 0..x_dim {
+            for y in 0..y_dim {
+                matrix.add(x, y, Some(x + 10 * y)).unwrap();
+            }
+        }
+
+        let row0: Vec<_> = matrix.row(0).unwrap().into_iter().copied().collect();
+        assert_eq!(row0, vec![Some(0), Some(1)]);
+
+        let col0: Vec<_> = matrix.col(0).unwrap().into_iter().copied().collect();
+        assert_eq!(col0, vec![Some(0), Some(10), Some(20), Some(30)]);
+    }
+
+    #[test]
+    fn test_row_col_iter_match_vec_variants() {
+        let mut matrix = Matrix::<usize>::new(3, 3);
+        for y in 0..matrix.y_dim {
+            matrix.add(y, 0, Some(1)).unwrap();
+        }
+
+        assert_eq!(
+            matrix.col_iter(0).unwrap().collect::<Vec<_>>(),
+            matrix.col(0).unwrap()
+        );
+        assert_eq!(
+            matrix.row_iter(0).unwrap().collect::<Vec<_>>(),
+            matrix.row(0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_col_row_iter_non_square() {
+        let (x_dim, y_dim) = (2, 4);
+        let mut matrix = Matrix::<usize>::new(x_dim, y_dim);
+        for x in 0..x_dim {
+            for y in 0..y_dim {
+                matrix.add(x, y, Some(x + 10 * y)).unwrap();
+            }
+        }
+
+        let row0: Vec<_> = matrix.row_iter(0).unwrap().copied().collect();
+        assert_eq!(row0, vec![Some(0), Some(1)]);
+
+        let col0: Vec<_> = matrix.col_iter(0).unwrap().copied().collect();
+        assert_eq!(col0, vec![Some(0), Some(10), Some(20), Some(30)]);
+    }
+
+    #[test]
+    fn test_iter_visits_every_cell() {
+        let mut matrix = Matrix::<usize>::new(2, 2);
+        matrix.add(0, 0, Some(1)).unwrap();
+
+        let mut cells: Vec<((usize, usize), Option<usize>)> = (&matrix)
+            .into_iter()
+            .map(|(pos, cell)| (pos, *cell))
+            .collect();
+        cells.sort();
+
+        assert_eq!(
+            cells,
+            vec![
+                ((0, 0), Some(1)),
+                ((0, 1), None),
+                ((1, 0), None),
+                ((1, 1), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_submatrix() {
+        let mut matrix = Matrix::<usize>::new(4, 4);
+        for i in 0..4 {
+            matrix.add(i, i, Some(i)).unwrap();
+        }
+
+        let view = matrix.submatrix(1..3, 1..3).unwrap();
+        assert_eq!(*view.get(0, 0).unwrap(), Some(1));
+        assert_eq!(*view.get(1, 1).unwrap(), Some(2));
+        assert!(view.get(2, 0).is_err());
+
+        let cells: Vec<_> = view.iter().collect();
+        assert_eq!(cells.len(), 4);
+    }
+
+    #[test]
+    fn test_submatrix_non_square_row_col_contents() {
+        let (x_dim, y_dim) = (3, 5);
+        let mut matrix = Matrix::<usize>::new(x_dim, y_dim);
+        for x in 0..x_dim {
+            for y in 0..y_dim {
+                matrix.add(x, y, Some(x + 10 * y)).unwrap();
+            }
+        }
+
+        let view = matrix.submatrix(1..3, 2..5).unwrap();
+
+        let row0: Vec<_> = view.row(0).unwrap().into_iter().copied().collect();
+        assert_eq!(row0, vec![Some(21), Some(22)]);
+
+        let col0: Vec<_> = view.col(0).unwrap().into_iter().copied().collect();
+        assert_eq!(col0, vec![Some(21), Some(31), Some(41)]);
+    }
+
+    #[test]
+    fn test_submatrix_out_of_range() {
+        let matrix = Matrix::<usize>::new(4, 4);
+        assert!(matrix.submatrix(0..5, 0..4).is_err());
+    }
+
+    #[test]
+    fn test_sparse_round_trip() {
+        let (x_dim, y_dim) = (8, 10);
+        let mut matrix = Matrix::<usize>::new(x_dim, y_dim);
+        matrix.add(2, 3, Some(42)).unwrap();
+        matrix.add(7, 9, Some(99)).unwrap();
+
+        let bytes = matrix.to_sparse_bytes().unwrap();
+        let restored = Matrix::<usize>::from_sparse_bytes(&bytes).unwrap();
+
+        assert_eq!(matrix.x_dim, restored.x_dim);
+        assert_eq!(matrix.y_dim, restored.y_dim);
+        assert_eq!(matrix.content, restored.content);
+    }
+
+    #[test]
+    fn test_sparse_bytes_are_smaller_when_sparse() {
+        let matrix = Matrix::<usize>::new(64, 64);
+        let sparse = matrix.to_sparse_bytes().unwrap();
+        let dense = serde_json::to_vec(&matrix).unwrap();
+
+        assert!(sparse.len() < dense.len());
+    }
+
+    #[test]
+    fn test_arbitrary() {
+        let bytes: Vec<u8> = (0..256).map(|b| b as u8).collect();
+        let mut unstructured = arbitrary::Unstructured::new(&bytes);
+        let matrix = Matrix::<u8>::arbitrary(&mut unstructured).unwrap();
+
+        assert!(matrix.x_dim <= MAX_DIM);
+        assert!(matrix.y_dim <= MAX_DIM);
+        assert_eq!(matrix.content.len(), matrix.y_dim);
+        for row in &matrix.content {
+            assert_eq!(row.len(), matrix.x_dim);
         }
     }
 }
@@ -0,0 +1,180 @@
+use crate::error::{Axis, BoundError};
+use std::fmt::Debug;
+
+/// Fixed-capacity counterpart to [`crate::matrix::Matrix`] for grids whose
+/// dimensions are known at compile time. Storage lives inline in
+/// `[[Option<T>; X]; Y]` instead of two levels of heap-allocated `Vec`,
+/// which avoids the extra allocation and pointer chasing that matters in
+/// tight loops over small, fixed-size grids. Use [`crate::matrix::Matrix`]
+/// instead when the dimensions are only known at runtime.
+#[derive(Debug)]
+pub struct StaticMatrix<T, const X: usize, const Y: usize> {
+    content: [[Option<T>; X]; Y],
+}
+
+impl<T: Debug, const X: usize, const Y: usize> StaticMatrix<T, X, Y> {
+    /// Constructor that initializes every cell of the inline `X` by `Y`
+    /// array to `None`
+    pub fn new() -> Self {
+        Self {
+            content: std::array::from_fn(|_| std::array::from_fn(|_| None)),
+        }
+    }
+
+    /// The number of columns, fixed at compile time
+    pub const fn x_dim(&self) -> usize {
+        X
+    }
+
+    /// The number of rows, fixed at compile time
+    pub const fn y_dim(&self) -> usize {
+        Y
+    }
+
+    /// Check that `x` and `y` are both in bounds, otherwise report which
+    /// axis was exceeded and by what maximum.
+    fn check_bounds(x: usize, y: usize) -> Result<(), BoundError> {
+        if x >= X {
+            return Err(BoundError::Exceed {
+                axis: Axis::X,
+                index: x,
+                max: X,
+            });
+        }
+        if y >= Y {
+            return Err(BoundError::Exceed {
+                axis: Axis::Y,
+                index: y,
+                max: Y,
+            });
+        }
+        Ok(())
+    }
+
+    /// Insert an element into the matrix at (x, y), indexing directly
+    /// into the inline array and returning a `BoundError` if either
+    /// index falls outside the compile-time dimensions
+    pub fn add(&mut self, x: usize, y: usize, value: Option<T>) -> Result<(), BoundError> {
+        Self::check_bounds(x, y)?;
+
+        self.content[y][x] = value;
+        Ok(())
+    }
+
+    /// Override an element from the matrix at (x, y), putting `None`
+    /// back in its place
+    pub fn remove(&mut self, x: usize, y: usize) -> Result<(), BoundError> {
+        self.add(x, y, None)
+    }
+
+    /// Get an immutable reference to an element at (x, y)
+    pub fn get(&self, x: usize, y: usize) -> Result<&Option<T>, BoundError> {
+        Self::check_bounds(x, y)?;
+
+        Ok(&self.content[y][x])
+    }
+
+    /// Get a mutable reference to an element at (x, y)
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Result<&mut Option<T>, BoundError> {
+        Self::check_bounds(x, y)?;
+
+        Ok(&mut self.content[y][x])
+    }
+
+    /// Obtain a Vector of immutable references to the underlying
+    /// container when given the column number
+    /// If the column number supplied is too large, exit and complain
+    /// through the use of the `BoundError`
+    pub fn col(&self, col: usize) -> Result<Vec<&Option<T>>, BoundError> {
+        if col >= X {
+            return Err(BoundError::Exceed {
+                axis: Axis::X,
+                index: col,
+                max: X,
+            });
+        }
+
+        Ok((0..Y).map(|row| &self.content[row][col]).collect())
+    }
+
+    /// Obtain a Vector of immutable references to the underlying
+    /// container when given the row number.
+    /// If the row number supplied is too large, exit and complain
+    /// through the use of the `BoundError`
+    pub fn row(&self, row: usize) -> Result<Vec<&Option<T>>, BoundError> {
+        if row >= Y {
+            return Err(BoundError::Exceed {
+                axis: Axis::Y,
+                index: row,
+                max: Y,
+            });
+        }
+
+        Ok((0..X).map(|col| &self.content[row][col]).collect())
+    }
+}
+
+impl<T: Debug, const X: usize, const Y: usize> Default for StaticMatrix<T, X, Y> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Ensure the dimensions of the container are correct
+    fn test_contstructor() {
+        let matrix = StaticMatrix::<usize, 8, 10>::new();
+
+        assert_eq!(8, matrix.x_dim());
+        assert_eq!(10, matrix.y_dim());
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut matrix = StaticMatrix::<usize, 8, 10>::new();
+
+        for x in 0..8 {
+            for y in 0..3 {
+                matrix.add(x, y, Some(x + y)).unwrap();
+            }
+        }
+
+        for x in 0..8 {
+            for y in 0..3 {
+                assert_eq!(*matrix.get(x, y).unwrap(), Some(x + y));
+            }
+        }
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut matrix = StaticMatrix::<usize, 8, 10>::new();
+        matrix.add(0, 0, Some(10)).unwrap();
+        matrix.remove(0, 0).unwrap();
+        assert_eq!(*matrix.get(0, 0).unwrap(), None);
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let mut matrix = StaticMatrix::<usize, 1, 1>::new();
+        let err = matrix.add(100, 100, Some(0_usize)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "index `100` exceeds the maximum of `1` along the `x` axis"
+        );
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let mut matrix = StaticMatrix::<usize, 2, 2>::new();
+        matrix.add(0, 0, Some(1)).unwrap();
+
+        *matrix.get_mut(0, 0).unwrap() = Some(2);
+
+        assert_eq!(*matrix.get(0, 0).unwrap(), Some(2));
+    }
+}